@@ -2,9 +2,15 @@ use fractic_env_config::{define_env_config, define_env_variable, EnvConfigEnum};
 
 define_env_variable!(COGNITO_REGION);
 define_env_variable!(COGNITO_USER_POOL_ID);
+// Optional override for the Cognito endpoint, used to point the client at a
+// local emulator (e.g. cognito-local) instead of the real AWS service.
+define_env_variable!(COGNITO_ENDPOINT_URL);
+define_env_variable!(COGNITO_IDENTITY_POOL_ID);
 
 define_env_config!(
     CognitoEnvConfig,
     CognitoRegion => COGNITO_REGION,
     CognitoUserPoolId => COGNITO_USER_POOL_ID,
+    CognitoEndpointUrl => COGNITO_ENDPOINT_URL,
+    CognitoIdentityPoolId => COGNITO_IDENTITY_POOL_ID,
 );
@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_config::BehaviorVersion;
+use aws_sdk_cognitoidentity::{
+    config::Region,
+    error::SdkError,
+    operation::{
+        get_credentials_for_identity::{
+            GetCredentialsForIdentityError, GetCredentialsForIdentityOutput,
+        },
+        get_id::{GetIdError, GetIdOutput},
+    },
+};
+use fractic_env_config::EnvVariables;
+use fractic_server_error::{CriticalError, ServerError};
+
+use crate::{env::CognitoEnvConfig, errors::CognitoCalloutError};
+
+// Temporary AWS credentials exchanged for an authenticated Cognito user, via
+// the identity pool's `GetId` + `GetCredentialsForIdentity` flow.
+// --------------------------------------------------
+
+pub struct CognitoCredentials {
+    pub access_key_id: String,
+    pub secret_key: String,
+    pub session_token: String,
+    pub expiration: Option<aws_smithy_types::DateTime>,
+}
+
+pub struct CognitoIdentityUtil<ClientImpl: CognitoIdentityClient> {
+    client: ClientImpl,
+    env: EnvVariables<CognitoEnvConfig>,
+}
+
+impl CognitoIdentityUtil<aws_sdk_cognitoidentity::Client> {
+    pub async fn new(
+        env: EnvVariables<CognitoEnvConfig>,
+    ) -> Result<CognitoIdentityUtil<aws_sdk_cognitoidentity::Client>, ServerError> {
+        let region_str = env.get(&CognitoEnvConfig::CognitoRegion)?;
+        let region = Region::new(region_str.clone());
+        let mut config_loader = aws_config::defaults(BehaviorVersion::v2024_03_28()).region(region);
+        if let Ok(endpoint_url) = env.get(&CognitoEnvConfig::CognitoEndpointUrl) {
+            config_loader = config_loader.endpoint_url(endpoint_url);
+        }
+        let shared_config = config_loader.load().await;
+        let client = aws_sdk_cognitoidentity::Client::new(&shared_config);
+        Ok(Self { client, env })
+    }
+}
+
+impl<ClientImpl: CognitoIdentityClient> CognitoIdentityUtil<ClientImpl> {
+    /// Exchanges a user-pool ID token for temporary, scoped AWS credentials,
+    /// via the configured identity pool.
+    pub async fn get_credentials_for_token(
+        &self,
+        id_token: &str,
+    ) -> Result<CognitoCredentials, ServerError> {
+        let identity_pool_id = self
+            .env
+            .get(&CognitoEnvConfig::CognitoIdentityPoolId)?
+            .clone();
+        let region = self.env.get(&CognitoEnvConfig::CognitoRegion)?.clone();
+        let user_pool_id = self.env.get(&CognitoEnvConfig::CognitoUserPoolId)?.clone();
+        let login_provider = format!("cognito-idp.{}.amazonaws.com/{}", region, user_pool_id);
+
+        let get_id_response = self
+            .client
+            .get_id(
+                identity_pool_id,
+                login_provider.clone(),
+                id_token.to_string(),
+            )
+            .await
+            .map_err(|e| CognitoCalloutError::with_debug(&e))?;
+        let identity_id = get_id_response
+            .identity_id
+            .ok_or(CriticalError::new("GetId did not return an identity ID."))?;
+
+        let credentials_response = self
+            .client
+            .get_credentials_for_identity(identity_id, login_provider, id_token.to_string())
+            .await
+            .map_err(|e| CognitoCalloutError::with_debug(&e))?;
+        let credentials = credentials_response.credentials.ok_or(CriticalError::new(
+            "GetCredentialsForIdentity did not return credentials.",
+        ))?;
+
+        Ok(CognitoCredentials {
+            access_key_id: credentials
+                .access_key_id
+                .ok_or(CriticalError::new("Credentials missing access key ID."))?,
+            secret_key: credentials
+                .secret_key
+                .ok_or(CriticalError::new("Credentials missing secret key."))?,
+            session_token: credentials
+                .session_token
+                .ok_or(CriticalError::new("Credentials missing session token."))?,
+            expiration: credentials.expiration,
+        })
+    }
+}
+
+// CognitoIdentityClient trait implementation.
+//
+// We wrap the regular cognito identity client in a custom
+// trait so that we can mock it in tests.
+// --------------------------------------------------
+
+#[async_trait]
+pub trait CognitoIdentityClient {
+    async fn get_id(
+        &self,
+        identity_pool_id: String,
+        login_provider: String,
+        id_token: String,
+    ) -> Result<GetIdOutput, SdkError<GetIdError>>;
+
+    async fn get_credentials_for_identity(
+        &self,
+        identity_id: String,
+        login_provider: String,
+        id_token: String,
+    ) -> Result<GetCredentialsForIdentityOutput, SdkError<GetCredentialsForIdentityError>>;
+}
+
+// Real client implementation.
+#[async_trait]
+impl CognitoIdentityClient for aws_sdk_cognitoidentity::Client {
+    async fn get_id(
+        &self,
+        identity_pool_id: String,
+        login_provider: String,
+        id_token: String,
+    ) -> Result<GetIdOutput, SdkError<GetIdError>> {
+        self.get_id()
+            .identity_pool_id(identity_pool_id)
+            .set_logins(Some(HashMap::from([(login_provider, id_token)])))
+            .send()
+            .await
+    }
+
+    async fn get_credentials_for_identity(
+        &self,
+        identity_id: String,
+        login_provider: String,
+        id_token: String,
+    ) -> Result<GetCredentialsForIdentityOutput, SdkError<GetCredentialsForIdentityError>> {
+        self.get_credentials_for_identity()
+            .identity_id(identity_id)
+            .set_logins(Some(HashMap::from([(login_provider, id_token)])))
+            .send()
+            .await
+    }
+}
+
+// Tests.
+// --------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::env::{COGNITO_IDENTITY_POOL_ID, COGNITO_REGION, COGNITO_USER_POOL_ID};
+
+    use super::*;
+    use aws_sdk_cognitoidentity::types::Credentials;
+    use fractic_core::collection;
+    use fractic_env_config::EnvVariables;
+
+    // Mock client implementation.
+    struct MockCognitoIdentityClient;
+    #[async_trait]
+    impl CognitoIdentityClient for MockCognitoIdentityClient {
+        async fn get_id(
+            &self,
+            _identity_pool_id: String,
+            _login_provider: String,
+            _id_token: String,
+        ) -> Result<GetIdOutput, SdkError<GetIdError>> {
+            Ok(GetIdOutput::builder().identity_id("identity-id").build())
+        }
+
+        async fn get_credentials_for_identity(
+            &self,
+            _identity_id: String,
+            _login_provider: String,
+            _id_token: String,
+        ) -> Result<GetCredentialsForIdentityOutput, SdkError<GetCredentialsForIdentityError>>
+        {
+            Ok(GetCredentialsForIdentityOutput::builder()
+                .credentials(
+                    Credentials::builder()
+                        .access_key_id("access-key")
+                        .secret_key("secret-key")
+                        .session_token("session-token")
+                        .build(),
+                )
+                .build())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_credentials_for_token() {
+        let mock_client = MockCognitoIdentityClient;
+        let env: EnvVariables<CognitoEnvConfig> = collection! {
+            COGNITO_REGION => "us-east-1".to_string(),
+            COGNITO_USER_POOL_ID => "us-east-1_123456789".to_string(),
+            COGNITO_IDENTITY_POOL_ID => "us-east-1:abcdef01-2345-6789-abcd-ef0123456789".to_string(),
+        };
+        let cognito_identity = CognitoIdentityUtil {
+            client: mock_client,
+            env,
+        };
+        let credentials = cognito_identity
+            .get_credentials_for_token("id-token")
+            .await
+            .unwrap();
+        assert_eq!(credentials.access_key_id, "access-key");
+        assert_eq!(credentials.secret_key, "secret-key");
+        assert_eq!(credentials.session_token, "session-token");
+    }
+}
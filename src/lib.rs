@@ -0,0 +1,4 @@
+pub mod env;
+pub mod errors;
+pub mod identity;
+pub mod util;
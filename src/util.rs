@@ -1,14 +1,31 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
-use aws_config::BehaviorVersion;
+use aws_config::{profile::ProfileFileCredentialsProvider, sts::AssumeRoleProvider, BehaviorVersion};
+use aws_credential_types::provider::SharedCredentialsProvider;
 use aws_sdk_cognitoidentityprovider::{
     config::Region,
     error::SdkError,
     operation::{
+        add_custom_attributes::{AddCustomAttributesError, AddCustomAttributesOutput},
+        admin_create_user::{AdminCreateUserError, AdminCreateUserOutput},
+        admin_delete_user::{AdminDeleteUserError, AdminDeleteUserOutput},
         admin_delete_user_attributes::{
             AdminDeleteUserAttributesError, AdminDeleteUserAttributesOutput,
         },
+        admin_disable_user::{AdminDisableUserError, AdminDisableUserOutput},
+        admin_enable_user::{AdminEnableUserError, AdminEnableUserOutput},
+        admin_get_user::{AdminGetUserError, AdminGetUserOutput},
+        admin_update_user_attributes::{
+            AdminUpdateUserAttributesError, AdminUpdateUserAttributesOutput,
+        },
         list_users::{ListUsersError, ListUsersOutput},
     },
+    types::{
+        AttributeDataType, AttributeType, DeliveryMediumType, MessageActionType,
+        NumberAttributeConstraintsType, SchemaAttributeType, StringAttributeConstraintsType,
+        UserType,
+    },
 };
 use fractic_env_config::EnvVariables;
 use fractic_server_error::{CriticalError, ServerError};
@@ -17,6 +34,99 @@ use crate::{env::CognitoEnvConfig, errors::CognitoCalloutError};
 
 const EMAIL_ATTRIBUTE: &str = "email";
 const USER_SUB_ATTRIBUTE: &str = "sub";
+const LIST_USERS_PAGE_SIZE: i32 = 60;
+const CUSTOM_ATTRIBUTE_PREFIX: &str = "custom:";
+
+// Attribute names built into every Cognito user pool; anything else is
+// assumed to be a caller-defined attribute and gets the "custom:" prefix.
+const STANDARD_ATTRIBUTES: &[&str] = &[
+    "sub",
+    "name",
+    "given_name",
+    "family_name",
+    "middle_name",
+    "nickname",
+    "preferred_username",
+    "profile",
+    "picture",
+    "website",
+    "email",
+    "email_verified",
+    "gender",
+    "birthdate",
+    "zoneinfo",
+    "locale",
+    "phone_number",
+    "phone_number_verified",
+    "address",
+    "updated_at",
+];
+
+// Resolved attributes for a single user, as returned by `admin_get_user`.
+pub struct CognitoAdminUser {
+    pub username: String,
+    pub enabled: bool,
+    pub user_status: Option<String>,
+    pub attributes: HashMap<String, String>,
+}
+
+// Definition of a custom attribute to add to a user pool's schema, via
+// `add_custom_attributes`.
+pub struct SchemaAttribute {
+    pub name: String,
+    pub data_type: AttributeDataType,
+    pub mutable: bool,
+    pub min: Option<String>,
+    pub max: Option<String>,
+}
+
+fn attribute_map_to_vec(attributes: HashMap<String, String>) -> Result<Vec<AttributeType>, ServerError> {
+    attributes
+        .into_iter()
+        .map(|(name, value)| {
+            AttributeType::builder()
+                .name(name)
+                .value(value)
+                .build()
+                .map_err(|e| CognitoCalloutError::with_debug(&e))
+        })
+        .collect()
+}
+
+// Caller-supplied attribute names are assumed to be custom unless they are
+// one of the pool's built-in standard attributes, or already prefixed.
+fn qualify_attribute_name(name: &str) -> String {
+    if name.starts_with(CUSTOM_ATTRIBUTE_PREFIX) || STANDARD_ATTRIBUTES.contains(&name) {
+        name.to_string()
+    } else {
+        format!("{}{}", CUSTOM_ATTRIBUTE_PREFIX, name)
+    }
+}
+
+fn schema_attribute_to_sdk(attribute: SchemaAttribute) -> Result<SchemaAttributeType, ServerError> {
+    let mut builder = SchemaAttributeType::builder()
+        .name(attribute.name)
+        .attribute_data_type(attribute.data_type.clone())
+        .mutable(attribute.mutable);
+
+    builder = match attribute.data_type {
+        AttributeDataType::Number => builder.number_attribute_constraints(
+            NumberAttributeConstraintsType::builder()
+                .set_min_value(attribute.min)
+                .set_max_value(attribute.max)
+                .build(),
+        ),
+        AttributeDataType::String => builder.string_attribute_constraints(
+            StringAttributeConstraintsType::builder()
+                .set_min_length(attribute.min)
+                .set_max_length(attribute.max)
+                .build(),
+        ),
+        _ => builder,
+    };
+
+    builder.build().map_err(|e| CognitoCalloutError::with_debug(&e))
+}
 
 // AWS Cognito utils.
 // --------------------------------------------------
@@ -29,16 +139,71 @@ pub struct CognitoUtil<ClientImpl: CognitoClient> {
 impl CognitoUtil<aws_sdk_cognitoidentityprovider::Client> {
     pub async fn new(
         env: EnvVariables<CognitoEnvConfig>,
+    ) -> Result<CognitoUtil<aws_sdk_cognitoidentityprovider::Client>, ServerError> {
+        let shared_config = Self::load_shared_config(&env, None).await?;
+        let client = aws_sdk_cognitoidentityprovider::Client::new(&shared_config);
+        Ok(Self { client, env })
+    }
+
+    /// Builds the client from credentials in a named profile of the shared
+    /// credentials file, rather than the process-wide default chain. Useful
+    /// for local multi-profile development against pools in different
+    /// accounts.
+    pub async fn new_with_profile(
+        env: EnvVariables<CognitoEnvConfig>,
+        profile_name: &str,
+    ) -> Result<CognitoUtil<aws_sdk_cognitoidentityprovider::Client>, ServerError> {
+        let credentials_provider = SharedCredentialsProvider::new(
+            ProfileFileCredentialsProvider::builder()
+                .profile_name(profile_name)
+                .build(),
+        );
+        let shared_config = Self::load_shared_config(&env, Some(credentials_provider)).await?;
+        let client = aws_sdk_cognitoidentityprovider::Client::new(&shared_config);
+        Ok(Self { client, env })
+    }
+
+    /// Builds the client from auto-refreshing STS AssumeRole credentials,
+    /// so a single binary can administer user pools owned by other accounts.
+    pub async fn new_with_assumed_role(
+        env: EnvVariables<CognitoEnvConfig>,
+        role_arn: &str,
+        session_name: &str,
     ) -> Result<CognitoUtil<aws_sdk_cognitoidentityprovider::Client>, ServerError> {
         let region_str = env.get(&CognitoEnvConfig::CognitoRegion)?;
         let region = Region::new(region_str.clone());
-        let shared_config = aws_config::defaults(BehaviorVersion::v2024_03_28())
-            .region(region)
+        let base_config = aws_config::defaults(BehaviorVersion::v2024_03_28())
+            .region(region.clone())
             .load()
             .await;
+        let credentials_provider = SharedCredentialsProvider::new(
+            AssumeRoleProvider::builder(role_arn)
+                .session_name(session_name)
+                .region(region)
+                .configure(&base_config)
+                .build()
+                .await,
+        );
+        let shared_config = Self::load_shared_config(&env, Some(credentials_provider)).await?;
         let client = aws_sdk_cognitoidentityprovider::Client::new(&shared_config);
         Ok(Self { client, env })
     }
+
+    async fn load_shared_config(
+        env: &EnvVariables<CognitoEnvConfig>,
+        credentials_provider: Option<SharedCredentialsProvider>,
+    ) -> Result<aws_config::SdkConfig, ServerError> {
+        let region_str = env.get(&CognitoEnvConfig::CognitoRegion)?;
+        let region = Region::new(region_str.clone());
+        let mut config_loader = aws_config::defaults(BehaviorVersion::v2024_03_28()).region(region);
+        if let Some(credentials_provider) = credentials_provider {
+            config_loader = config_loader.credentials_provider(credentials_provider);
+        }
+        if let Ok(endpoint_url) = env.get(&CognitoEnvConfig::CognitoEndpointUrl) {
+            config_loader = config_loader.endpoint_url(endpoint_url);
+        }
+        Ok(config_loader.load().await)
+    }
 }
 
 impl<ClientImpl: CognitoClient> CognitoUtil<ClientImpl> {
@@ -51,7 +216,12 @@ impl<ClientImpl: CognitoClient> CognitoUtil<ClientImpl> {
 
         let response = self
             .client
-            .list_users(user_pool_id, format!("{} = \"{}\"", attribute, value), 1)
+            .list_users(
+                user_pool_id,
+                Some(format!("{} = \"{}\"", attribute, value)),
+                1,
+                None,
+            )
             .await
             .map_err(|e| CognitoCalloutError::with_debug(&e))?;
 
@@ -76,6 +246,49 @@ impl<ClientImpl: CognitoClient> CognitoUtil<ClientImpl> {
             .await
     }
 
+    /// Drives `list_users` to completion, following `pagination_token` across
+    /// requests until the pool is exhausted, and accumulates every matching
+    /// user along the way.
+    pub async fn list_all_users(
+        &self,
+        filter: Option<String>,
+    ) -> Result<Vec<UserType>, ServerError> {
+        let user_pool_id = self.env.get(&CognitoEnvConfig::CognitoUserPoolId)?.clone();
+
+        let mut users = Vec::new();
+        let mut pagination_token = None;
+        loop {
+            let response = self
+                .client
+                .list_users(
+                    user_pool_id.clone(),
+                    filter.clone(),
+                    LIST_USERS_PAGE_SIZE,
+                    pagination_token,
+                )
+                .await
+                .map_err(|e| CognitoCalloutError::with_debug(&e))?;
+
+            users.extend(response.users.unwrap_or_default());
+
+            pagination_token = response.pagination_token;
+            if pagination_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(users)
+    }
+
+    pub async fn find_users_by_attribute(
+        &self,
+        attribute: &str,
+        value: &str,
+    ) -> Result<Vec<UserType>, ServerError> {
+        self.list_all_users(Some(format!("{} = \"{}\"", attribute, value)))
+            .await
+    }
+
     pub async fn delete_email_for_user(&self, user_sub: &str) -> Result<(), ServerError> {
         let user_pool_id = self.env.get(&CognitoEnvConfig::CognitoUserPoolId)?.clone();
 
@@ -95,6 +308,154 @@ impl<ClientImpl: CognitoClient> CognitoUtil<ClientImpl> {
 
         Ok(())
     }
+
+    pub async fn admin_create_user(
+        &self,
+        username: &str,
+        attributes: HashMap<String, String>,
+        desired_delivery_mediums: Option<Vec<DeliveryMediumType>>,
+        message_action: Option<MessageActionType>,
+    ) -> Result<(), ServerError> {
+        let user_pool_id = self.env.get(&CognitoEnvConfig::CognitoUserPoolId)?.clone();
+
+        self.client
+            .admin_create_user(
+                user_pool_id,
+                username.to_string(),
+                attribute_map_to_vec(attributes)?,
+                desired_delivery_mediums,
+                message_action,
+            )
+            .await
+            .map_err(|e| CognitoCalloutError::with_debug(&e))?;
+
+        Ok(())
+    }
+
+    pub async fn admin_get_user(&self, username: &str) -> Result<CognitoAdminUser, ServerError> {
+        let user_pool_id = self.env.get(&CognitoEnvConfig::CognitoUserPoolId)?.clone();
+
+        let response = self
+            .client
+            .admin_get_user(user_pool_id, username.to_string())
+            .await
+            .map_err(|e| CognitoCalloutError::with_debug(&e))?;
+
+        let attributes = response
+            .user_attributes
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|attribute| Some((attribute.name, attribute.value?)))
+            .collect();
+
+        Ok(CognitoAdminUser {
+            username: response.username.ok_or(CriticalError::new(&format!(
+                "User found but username is missing (username: '{}').",
+                username
+            )))?,
+            enabled: response.enabled,
+            user_status: response.user_status.map(|status| status.as_str().to_string()),
+            attributes,
+        })
+    }
+
+    pub async fn admin_update_user_attributes(
+        &self,
+        username: &str,
+        attributes: HashMap<String, String>,
+    ) -> Result<(), ServerError> {
+        let user_pool_id = self.env.get(&CognitoEnvConfig::CognitoUserPoolId)?.clone();
+
+        self.client
+            .admin_update_user_attributes(
+                user_pool_id,
+                username.to_string(),
+                attribute_map_to_vec(attributes)?,
+            )
+            .await
+            .map_err(|e| CognitoCalloutError::with_debug(&e))?;
+
+        Ok(())
+    }
+
+    pub async fn admin_disable_user(&self, username: &str) -> Result<(), ServerError> {
+        let user_pool_id = self.env.get(&CognitoEnvConfig::CognitoUserPoolId)?.clone();
+
+        self.client
+            .admin_disable_user(user_pool_id, username.to_string())
+            .await
+            .map_err(|e| CognitoCalloutError::with_debug(&e))?;
+
+        Ok(())
+    }
+
+    pub async fn admin_enable_user(&self, username: &str) -> Result<(), ServerError> {
+        let user_pool_id = self.env.get(&CognitoEnvConfig::CognitoUserPoolId)?.clone();
+
+        self.client
+            .admin_enable_user(user_pool_id, username.to_string())
+            .await
+            .map_err(|e| CognitoCalloutError::with_debug(&e))?;
+
+        Ok(())
+    }
+
+    pub async fn admin_delete_user(&self, username: &str) -> Result<(), ServerError> {
+        let user_pool_id = self.env.get(&CognitoEnvConfig::CognitoUserPoolId)?.clone();
+
+        self.client
+            .admin_delete_user(user_pool_id, username.to_string())
+            .await
+            .map_err(|e| CognitoCalloutError::with_debug(&e))?;
+
+        Ok(())
+    }
+
+    /// Adds custom attributes to the user pool's schema. Note that, unlike
+    /// standard attributes, custom attributes cannot be removed once added.
+    pub async fn add_custom_attributes(
+        &self,
+        attributes: Vec<SchemaAttribute>,
+    ) -> Result<(), ServerError> {
+        let user_pool_id = self.env.get(&CognitoEnvConfig::CognitoUserPoolId)?.clone();
+        let attributes = attributes
+            .into_iter()
+            .map(schema_attribute_to_sdk)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.client
+            .add_custom_attributes(user_pool_id, attributes)
+            .await
+            .map_err(|e| CognitoCalloutError::with_debug(&e))?;
+
+        Ok(())
+    }
+
+    /// Reads a single attribute for a user, auto-prefixing `name` with
+    /// "custom:" unless it is one of the pool's standard attributes.
+    pub async fn get_user_attribute(
+        &self,
+        username: &str,
+        name: &str,
+    ) -> Result<Option<String>, ServerError> {
+        let user = self.admin_get_user(username).await?;
+        Ok(user.attributes.get(&qualify_attribute_name(name)).cloned())
+    }
+
+    /// Writes a single attribute for a user, auto-prefixing `name` with
+    /// "custom:" unless it is one of the pool's standard attributes.
+    pub async fn set_user_attribute(
+        &self,
+        username: &str,
+        name: &str,
+        value: &str,
+    ) -> Result<(), ServerError> {
+        self.admin_update_user_attributes(
+            username,
+            HashMap::from([(qualify_attribute_name(name), value.to_string())]),
+        )
+        .await
+    }
 }
 
 // CognitoClient trait implementation.
@@ -108,8 +469,9 @@ pub trait CognitoClient {
     async fn list_users(
         &self,
         user_pool_id: String,
-        filter: String,
+        filter: Option<String>,
         limit: i32,
+        pagination_token: Option<String>,
     ) -> Result<ListUsersOutput, SdkError<ListUsersError>>;
 
     async fn admin_delete_user_attributes(
@@ -118,6 +480,52 @@ pub trait CognitoClient {
         username: String,
         attributes: Vec<String>,
     ) -> Result<AdminDeleteUserAttributesOutput, SdkError<AdminDeleteUserAttributesError>>;
+
+    async fn admin_create_user(
+        &self,
+        user_pool_id: String,
+        username: String,
+        attributes: Vec<AttributeType>,
+        desired_delivery_mediums: Option<Vec<DeliveryMediumType>>,
+        message_action: Option<MessageActionType>,
+    ) -> Result<AdminCreateUserOutput, SdkError<AdminCreateUserError>>;
+
+    async fn admin_get_user(
+        &self,
+        user_pool_id: String,
+        username: String,
+    ) -> Result<AdminGetUserOutput, SdkError<AdminGetUserError>>;
+
+    async fn admin_update_user_attributes(
+        &self,
+        user_pool_id: String,
+        username: String,
+        attributes: Vec<AttributeType>,
+    ) -> Result<AdminUpdateUserAttributesOutput, SdkError<AdminUpdateUserAttributesError>>;
+
+    async fn admin_disable_user(
+        &self,
+        user_pool_id: String,
+        username: String,
+    ) -> Result<AdminDisableUserOutput, SdkError<AdminDisableUserError>>;
+
+    async fn admin_enable_user(
+        &self,
+        user_pool_id: String,
+        username: String,
+    ) -> Result<AdminEnableUserOutput, SdkError<AdminEnableUserError>>;
+
+    async fn admin_delete_user(
+        &self,
+        user_pool_id: String,
+        username: String,
+    ) -> Result<AdminDeleteUserOutput, SdkError<AdminDeleteUserError>>;
+
+    async fn add_custom_attributes(
+        &self,
+        user_pool_id: String,
+        attributes: Vec<SchemaAttributeType>,
+    ) -> Result<AddCustomAttributesOutput, SdkError<AddCustomAttributesError>>;
 }
 
 // Real client implementation.
@@ -126,13 +534,15 @@ impl CognitoClient for aws_sdk_cognitoidentityprovider::Client {
     async fn list_users(
         &self,
         user_pool_id: String,
-        filter: String,
+        filter: Option<String>,
         limit: i32,
+        pagination_token: Option<String>,
     ) -> Result<ListUsersOutput, SdkError<ListUsersError>> {
         self.list_users()
             .user_pool_id(user_pool_id)
-            .set_filter(Some(filter.to_string()))
+            .set_filter(filter)
             .set_limit(Some(limit))
+            .set_pagination_token(pagination_token)
             .send()
             .await
     }
@@ -150,6 +560,98 @@ impl CognitoClient for aws_sdk_cognitoidentityprovider::Client {
             .send()
             .await
     }
+
+    async fn admin_create_user(
+        &self,
+        user_pool_id: String,
+        username: String,
+        attributes: Vec<AttributeType>,
+        desired_delivery_mediums: Option<Vec<DeliveryMediumType>>,
+        message_action: Option<MessageActionType>,
+    ) -> Result<AdminCreateUserOutput, SdkError<AdminCreateUserError>> {
+        self.admin_create_user()
+            .user_pool_id(user_pool_id)
+            .username(username)
+            .set_user_attributes(Some(attributes))
+            .set_desired_delivery_mediums(desired_delivery_mediums)
+            .set_message_action(message_action)
+            .send()
+            .await
+    }
+
+    async fn admin_get_user(
+        &self,
+        user_pool_id: String,
+        username: String,
+    ) -> Result<AdminGetUserOutput, SdkError<AdminGetUserError>> {
+        self.admin_get_user()
+            .user_pool_id(user_pool_id)
+            .username(username)
+            .send()
+            .await
+    }
+
+    async fn admin_update_user_attributes(
+        &self,
+        user_pool_id: String,
+        username: String,
+        attributes: Vec<AttributeType>,
+    ) -> Result<AdminUpdateUserAttributesOutput, SdkError<AdminUpdateUserAttributesError>> {
+        self.admin_update_user_attributes()
+            .user_pool_id(user_pool_id)
+            .username(username)
+            .set_user_attributes(Some(attributes))
+            .send()
+            .await
+    }
+
+    async fn admin_disable_user(
+        &self,
+        user_pool_id: String,
+        username: String,
+    ) -> Result<AdminDisableUserOutput, SdkError<AdminDisableUserError>> {
+        self.admin_disable_user()
+            .user_pool_id(user_pool_id)
+            .username(username)
+            .send()
+            .await
+    }
+
+    async fn admin_enable_user(
+        &self,
+        user_pool_id: String,
+        username: String,
+    ) -> Result<AdminEnableUserOutput, SdkError<AdminEnableUserError>> {
+        self.admin_enable_user()
+            .user_pool_id(user_pool_id)
+            .username(username)
+            .send()
+            .await
+    }
+
+    async fn admin_delete_user(
+        &self,
+        user_pool_id: String,
+        username: String,
+    ) -> Result<AdminDeleteUserOutput, SdkError<AdminDeleteUserError>> {
+        self.admin_delete_user()
+            .user_pool_id(user_pool_id)
+            .username(username)
+            .send()
+            .await
+    }
+
+    async fn add_custom_attributes(
+        &self,
+        user_pool_id: String,
+        attributes: Vec<SchemaAttributeType>,
+    ) -> Result<AddCustomAttributesOutput, SdkError<AddCustomAttributesError>> {
+        self.add_custom_attributes()
+            .user_pool_id(user_pool_id)
+            .set_custom_attributes(Some(attributes))
+            .send()
+            .await
+    }
 }
 
 // Tests.
@@ -160,7 +662,6 @@ mod tests {
     use crate::env::{COGNITO_REGION, COGNITO_USER_POOL_ID};
 
     use super::*;
-    use aws_sdk_cognitoidentityprovider::types::UserType;
     use fractic_core::collection;
     use fractic_env_config::EnvVariables;
 
@@ -173,12 +674,21 @@ mod tests {
         async fn list_users(
             &self,
             _user_pool_id: String,
-            _filter: String,
+            _filter: Option<String>,
             _limit: i32,
+            pagination_token: Option<String>,
         ) -> Result<ListUsersOutput, SdkError<ListUsersError>> {
             let mut builder = ListUsersOutput::builder();
             if self.should_find_user {
-                builder = builder.users(UserType::builder().username("username").build());
+                builder = match pagination_token.as_deref() {
+                    None => builder
+                        .users(UserType::builder().username("username").build())
+                        .pagination_token("next-page"),
+                    Some("next-page") => {
+                        builder.users(UserType::builder().username("username2").build())
+                    }
+                    Some(_) => builder,
+                };
             };
             Ok(builder.build())
         }
@@ -193,6 +703,77 @@ mod tests {
             let builder = AdminDeleteUserAttributesOutput::builder();
             Ok(builder.build())
         }
+
+        async fn admin_create_user(
+            &self,
+            _user_pool_id: String,
+            _username: String,
+            _attributes: Vec<AttributeType>,
+            _desired_delivery_mediums: Option<Vec<DeliveryMediumType>>,
+            _message_action: Option<MessageActionType>,
+        ) -> Result<AdminCreateUserOutput, SdkError<AdminCreateUserError>> {
+            Ok(AdminCreateUserOutput::builder().build())
+        }
+
+        async fn admin_get_user(
+            &self,
+            _user_pool_id: String,
+            username: String,
+        ) -> Result<AdminGetUserOutput, SdkError<AdminGetUserError>> {
+            Ok(AdminGetUserOutput::builder()
+                .username(username)
+                .enabled(true)
+                .user_attributes(
+                    AttributeType::builder()
+                        .name("email")
+                        .value("abc@example.com")
+                        .build()
+                        .unwrap(),
+                )
+                .build())
+        }
+
+        async fn admin_update_user_attributes(
+            &self,
+            _user_pool_id: String,
+            _username: String,
+            _attributes: Vec<AttributeType>,
+        ) -> Result<AdminUpdateUserAttributesOutput, SdkError<AdminUpdateUserAttributesError>>
+        {
+            Ok(AdminUpdateUserAttributesOutput::builder().build())
+        }
+
+        async fn admin_disable_user(
+            &self,
+            _user_pool_id: String,
+            _username: String,
+        ) -> Result<AdminDisableUserOutput, SdkError<AdminDisableUserError>> {
+            Ok(AdminDisableUserOutput::builder().build())
+        }
+
+        async fn admin_enable_user(
+            &self,
+            _user_pool_id: String,
+            _username: String,
+        ) -> Result<AdminEnableUserOutput, SdkError<AdminEnableUserError>> {
+            Ok(AdminEnableUserOutput::builder().build())
+        }
+
+        async fn admin_delete_user(
+            &self,
+            _user_pool_id: String,
+            _username: String,
+        ) -> Result<AdminDeleteUserOutput, SdkError<AdminDeleteUserError>> {
+            Ok(AdminDeleteUserOutput::builder().build())
+        }
+
+        async fn add_custom_attributes(
+            &self,
+            _user_pool_id: String,
+            _attributes: Vec<SchemaAttributeType>,
+        ) -> Result<AddCustomAttributesOutput, SdkError<AddCustomAttributesError>> {
+            Ok(AddCustomAttributesOutput::builder().build())
+        }
     }
 
     #[tokio::test]
@@ -234,4 +815,233 @@ mod tests {
             .unwrap();
         assert_eq!(username, None);
     }
+
+    #[tokio::test]
+    async fn test_admin_create_user() {
+        let mock_client = MockCognitoClient {
+            should_find_user: true,
+        };
+        let env: EnvVariables<CognitoEnvConfig> = collection! {
+            COGNITO_REGION => "us-east-1".to_string(),
+            COGNITO_USER_POOL_ID => "us-east-1_123456789".to_string(),
+        };
+        let cognito = CognitoUtil {
+            client: mock_client,
+            env,
+        };
+        cognito
+            .admin_create_user(
+                "username",
+                collection! { "email".to_string() => "abc@example.com".to_string() },
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_admin_get_user() {
+        let mock_client = MockCognitoClient {
+            should_find_user: true,
+        };
+        let env: EnvVariables<CognitoEnvConfig> = collection! {
+            COGNITO_REGION => "us-east-1".to_string(),
+            COGNITO_USER_POOL_ID => "us-east-1_123456789".to_string(),
+        };
+        let cognito = CognitoUtil {
+            client: mock_client,
+            env,
+        };
+        let user = cognito.admin_get_user("username").await.unwrap();
+        assert_eq!(user.username, "username");
+        assert!(user.enabled);
+        assert_eq!(
+            user.attributes.get("email"),
+            Some(&"abc@example.com".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_admin_update_user_attributes() {
+        let mock_client = MockCognitoClient {
+            should_find_user: true,
+        };
+        let env: EnvVariables<CognitoEnvConfig> = collection! {
+            COGNITO_REGION => "us-east-1".to_string(),
+            COGNITO_USER_POOL_ID => "us-east-1_123456789".to_string(),
+        };
+        let cognito = CognitoUtil {
+            client: mock_client,
+            env,
+        };
+        cognito
+            .admin_update_user_attributes(
+                "username",
+                collection! { "email".to_string() => "new@example.com".to_string() },
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_admin_disable_and_enable_user() {
+        let mock_client = MockCognitoClient {
+            should_find_user: true,
+        };
+        let env: EnvVariables<CognitoEnvConfig> = collection! {
+            COGNITO_REGION => "us-east-1".to_string(),
+            COGNITO_USER_POOL_ID => "us-east-1_123456789".to_string(),
+        };
+        let cognito = CognitoUtil {
+            client: mock_client,
+            env,
+        };
+        cognito.admin_disable_user("username").await.unwrap();
+        cognito.admin_enable_user("username").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_admin_delete_user() {
+        let mock_client = MockCognitoClient {
+            should_find_user: true,
+        };
+        let env: EnvVariables<CognitoEnvConfig> = collection! {
+            COGNITO_REGION => "us-east-1".to_string(),
+            COGNITO_USER_POOL_ID => "us-east-1_123456789".to_string(),
+        };
+        let cognito = CognitoUtil {
+            client: mock_client,
+            env,
+        };
+        cognito.admin_delete_user("username").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_list_all_users_paginates() {
+        let mock_client = MockCognitoClient {
+            should_find_user: true,
+        };
+        let env: EnvVariables<CognitoEnvConfig> = collection! {
+            COGNITO_REGION => "us-east-1".to_string(),
+            COGNITO_USER_POOL_ID => "us-east-1_123456789".to_string(),
+        };
+        let cognito = CognitoUtil {
+            client: mock_client,
+            env,
+        };
+        let users = cognito.list_all_users(None).await.unwrap();
+        let usernames: Vec<_> = users.into_iter().filter_map(|u| u.username).collect();
+        assert_eq!(usernames, vec!["username".to_string(), "username2".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_find_users_by_attribute_returns_all_matches() {
+        let mock_client = MockCognitoClient {
+            should_find_user: true,
+        };
+        let env: EnvVariables<CognitoEnvConfig> = collection! {
+            COGNITO_REGION => "us-east-1".to_string(),
+            COGNITO_USER_POOL_ID => "us-east-1_123456789".to_string(),
+        };
+        let cognito = CognitoUtil {
+            client: mock_client,
+            env,
+        };
+        let users = cognito
+            .find_users_by_attribute("email", "abc@example.com")
+            .await
+            .unwrap();
+        assert_eq!(users.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_add_custom_attributes() {
+        let mock_client = MockCognitoClient {
+            should_find_user: true,
+        };
+        let env: EnvVariables<CognitoEnvConfig> = collection! {
+            COGNITO_REGION => "us-east-1".to_string(),
+            COGNITO_USER_POOL_ID => "us-east-1_123456789".to_string(),
+        };
+        let cognito = CognitoUtil {
+            client: mock_client,
+            env,
+        };
+        cognito
+            .add_custom_attributes(vec![SchemaAttribute {
+                name: "favorite_color".to_string(),
+                data_type: AttributeDataType::String,
+                mutable: true,
+                min: Some("1".to_string()),
+                max: Some("256".to_string()),
+            }])
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_user_attribute_standard() {
+        let mock_client = MockCognitoClient {
+            should_find_user: true,
+        };
+        let env: EnvVariables<CognitoEnvConfig> = collection! {
+            COGNITO_REGION => "us-east-1".to_string(),
+            COGNITO_USER_POOL_ID => "us-east-1_123456789".to_string(),
+        };
+        let cognito = CognitoUtil {
+            client: mock_client,
+            env,
+        };
+        let value = cognito.get_user_attribute("username", "email").await.unwrap();
+        assert_eq!(value, Some("abc@example.com".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_get_user_attribute_custom_not_found() {
+        let mock_client = MockCognitoClient {
+            should_find_user: true,
+        };
+        let env: EnvVariables<CognitoEnvConfig> = collection! {
+            COGNITO_REGION => "us-east-1".to_string(),
+            COGNITO_USER_POOL_ID => "us-east-1_123456789".to_string(),
+        };
+        let cognito = CognitoUtil {
+            client: mock_client,
+            env,
+        };
+        // Mock only ever returns the "email" attribute, so a caller-defined
+        // attribute (auto-prefixed to "custom:favorite_color") is absent.
+        let value = cognito
+            .get_user_attribute("username", "favorite_color")
+            .await
+            .unwrap();
+        assert_eq!(value, None);
+    }
+
+    #[tokio::test]
+    async fn test_set_user_attribute() {
+        let mock_client = MockCognitoClient {
+            should_find_user: true,
+        };
+        let env: EnvVariables<CognitoEnvConfig> = collection! {
+            COGNITO_REGION => "us-east-1".to_string(),
+            COGNITO_USER_POOL_ID => "us-east-1_123456789".to_string(),
+        };
+        let cognito = CognitoUtil {
+            client: mock_client,
+            env,
+        };
+        cognito
+            .set_user_attribute("username", "favorite_color", "blue")
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn test_qualify_attribute_name() {
+        assert_eq!(qualify_attribute_name("email"), "email");
+        assert_eq!(qualify_attribute_name("favorite_color"), "custom:favorite_color");
+        assert_eq!(qualify_attribute_name("custom:already_qualified"), "custom:already_qualified");
+    }
 }